@@ -0,0 +1,187 @@
+use lambda_http::{Body, Error, Request, Response};
+use serde::{Deserialize, Serialize};
+use aws_sdk_cognitoidentityprovider::Client as CognitoClient;
+use aws_sdk_cognitoidentityprovider::types::AttributeType;
+use aws_config::load_defaults;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use tracing::{info, error};
+
+type HmacSha256 = Hmac<Sha256>;
+
+#[derive(Deserialize)]
+struct ConfirmUserRequest {
+    user_id: String,
+}
+
+#[derive(Deserialize)]
+struct ResendCodeRequest {
+    user_id: String,
+}
+
+#[derive(Serialize)]
+struct MessageResponse {
+    message: String,
+}
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String,
+    message: String,
+}
+
+pub(crate) async fn function_handler(event: Request) -> Result<Response<Body>, Error> {
+    info!("Processing email confirmation request");
+
+    let body = event.body();
+    let request: ConfirmUserRequest = match serde_json::from_slice(body) {
+        Ok(req) => req,
+        Err(e) => {
+            error!("Invalid request body: {}", e);
+            return Ok(create_error_response(400, "INVALID_REQUEST", "Invalid request body"));
+        }
+    };
+
+    if request.user_id.is_empty() {
+        return Ok(create_error_response(400, "VALIDATION_ERROR", "user_id is required"));
+    }
+
+    let config = load_defaults(aws_config::BehaviorVersion::latest()).await;
+    let cognito_client = CognitoClient::new(&config);
+
+    let user_pool_id = std::env::var("USER_POOL_ID")
+        .map_err(|_| "USER_POOL_ID environment variable not set")?;
+
+    // Accounts are provisioned via `admin_create_user` with message delivery
+    // suppressed, so they are already CONFIRMED and no verification code is ever
+    // emailed. Flip `email_verified` directly with admin credentials rather than
+    // going through the self-service `confirm_sign_up` code flow.
+    match cognito_client
+        .admin_update_user_attributes()
+        .user_pool_id(&user_pool_id)
+        .username(&request.user_id)
+        .user_attributes(
+            AttributeType::builder()
+                .name("email_verified")
+                .value("true")
+                .build()?,
+        )
+        .send()
+        .await
+    {
+        Ok(_) => {
+            info!("Email marked verified for user: {}", request.user_id);
+            Ok(create_success_response(MessageResponse {
+                message: "Email confirmed successfully".to_string(),
+            }))
+        }
+        Err(e) => {
+            error!("Failed to confirm user: {}", e);
+            Ok(create_error_response(500, "CONFIRMATION_FAILED", &e.to_string()))
+        }
+    }
+}
+
+/// Re-sends the Cognito confirmation code for a user.
+///
+/// This is only meaningful when the user pool is configured for self-service
+/// sign-up with email delivery. Under this system's admin-create/suppress
+/// provisioning no code is emitted in the first place — see
+/// [`function_handler`], which verifies the address administratively — so this
+/// endpoint exists for pools that opt into the self-service flow.
+pub(crate) async fn resend_confirmation_code(event: Request) -> Result<Response<Body>, Error> {
+    info!("Processing resend confirmation code request");
+
+    let body = event.body();
+    let request: ResendCodeRequest = match serde_json::from_slice(body) {
+        Ok(req) => req,
+        Err(e) => {
+            error!("Invalid request body: {}", e);
+            return Ok(create_error_response(400, "INVALID_REQUEST", "Invalid request body"));
+        }
+    };
+
+    if request.user_id.is_empty() {
+        return Ok(create_error_response(400, "VALIDATION_ERROR", "user_id is required"));
+    }
+
+    let config = load_defaults(aws_config::BehaviorVersion::latest()).await;
+    let cognito_client = CognitoClient::new(&config);
+
+    let client_id = std::env::var("CLIENT_ID")
+        .map_err(|_| "CLIENT_ID environment variable not set")?;
+    let client_secret = std::env::var("CLIENT_SECRET").ok();
+
+    let mut builder = cognito_client
+        .resend_confirmation_code()
+        .client_id(&client_id)
+        .username(&request.user_id);
+
+    if let Some(secret) = client_secret.as_deref() {
+        builder = builder.secret_hash(compute_secret_hash(&request.user_id, &client_id, secret));
+    }
+
+    match builder.send().await {
+        Ok(_) => Ok(create_success_response(MessageResponse {
+            message: "Confirmation code resent".to_string(),
+        })),
+        Err(e) => {
+            error!("Failed to resend confirmation code: {}", e);
+            Ok(create_error_response(500, "RESEND_FAILED", &e.to_string()))
+        }
+    }
+}
+
+/// Derives the `SECRET_HASH` required by app clients that carry a secret:
+/// `base64(HMAC-SHA256(client_secret, username + client_id))`.
+fn compute_secret_hash(username: &str, client_id: &str, client_secret: &str) -> String {
+    let mut mac = HmacSha256::new_from_slice(client_secret.as_bytes())
+        .expect("HMAC can take a key of any size");
+    mac.update(username.as_bytes());
+    mac.update(client_id.as_bytes());
+    base64::engine::general_purpose::STANDARD.encode(mac.finalize().into_bytes())
+}
+
+fn create_success_response<T: Serialize>(data: T) -> Response<Body> {
+    Response::builder()
+        .status(200)
+        .header("content-type", "application/json")
+        .body(serde_json::to_string(&data).unwrap().into())
+        .unwrap()
+}
+
+fn create_error_response(status: u16, error_code: &str, message: &str) -> Response<Body> {
+    let error_response = ErrorResponse {
+        error: error_code.to_string(),
+        message: message.to_string(),
+    };
+
+    Response::builder()
+        .status(status)
+        .header("content-type", "application/json")
+        .body(serde_json::to_string(&error_response).unwrap().into())
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lambda_http::Request;
+
+    #[tokio::test]
+    async fn test_invalid_request_body() {
+        let request = Request::new(Body::Empty);
+        let response = function_handler(request).await.unwrap();
+        assert_eq!(response.status(), 400);
+    }
+
+    #[tokio::test]
+    async fn test_missing_required_fields() {
+        let body = r#"{"user_id": ""}"#;
+        let request = Request::new(body.into());
+
+        let response = function_handler(request).await.unwrap();
+        assert_eq!(response.status(), 400);
+    }
+}
@@ -0,0 +1,187 @@
+use lambda_http::{Body, Error, Request, Response};
+use serde::{Deserialize, Serialize};
+use aws_sdk_cognitoidentityprovider::Client as CognitoClient;
+use aws_sdk_cognitoidentityprovider::types::AuthFlowType;
+use aws_config::load_defaults;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use tracing::{info, error};
+
+type HmacSha256 = Hmac<Sha256>;
+
+#[derive(Deserialize)]
+struct LoginUserRequest {
+    email: String,
+    password: String,
+}
+
+#[derive(Serialize)]
+struct LoginUserResponse {
+    id_token: Option<String>,
+    access_token: Option<String>,
+    refresh_token: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String,
+    message: String,
+}
+
+pub(crate) async fn function_handler(event: Request) -> Result<Response<Body>, Error> {
+    info!("Processing user login request");
+
+    // Parse the request body
+    let body = event.body();
+    let request: LoginUserRequest = match serde_json::from_slice(body) {
+        Ok(req) => req,
+        Err(e) => {
+            error!("Invalid request body: {}", e);
+            return Ok(create_error_response(400, "INVALID_REQUEST", "Invalid request body"));
+        }
+    };
+
+    // Validate input
+    if request.email.is_empty() || request.password.is_empty() {
+        return Ok(create_error_response(400, "VALIDATION_ERROR", "Email and password are required"));
+    }
+
+    // Initialize AWS Cognito client
+    let config = load_defaults(aws_config::BehaviorVersion::latest()).await;
+    let cognito_client = CognitoClient::new(&config);
+
+    // Read pool/app-client configuration from environment
+    let user_pool_id = std::env::var("USER_POOL_ID")
+        .map_err(|_| "USER_POOL_ID environment variable not set")?;
+    let client_id = std::env::var("CLIENT_ID")
+        .map_err(|_| "CLIENT_ID environment variable not set")?;
+    // A client secret is only configured for confidential app clients; when it
+    // is absent the SECRET_HASH auth parameter must be omitted entirely.
+    let client_secret = std::env::var("CLIENT_SECRET").ok();
+
+    match sign_in_with_cognito(
+        &cognito_client,
+        &user_pool_id,
+        &client_id,
+        client_secret.as_deref(),
+        &request.email,
+        &request.password,
+    ).await {
+        Ok(result) => {
+            info!("User signed in successfully: {}", request.email);
+            Ok(create_success_response(LoginUserResponse {
+                id_token: result.id_token,
+                access_token: result.access_token,
+                refresh_token: result.refresh_token,
+            }))
+        }
+        Err(e) => {
+            error!("Failed to sign in user: {}", e);
+            Ok(create_error_response(401, "AUTHENTICATION_FAILED", &e.to_string()))
+        }
+    }
+}
+
+struct AuthTokens {
+    id_token: Option<String>,
+    access_token: Option<String>,
+    refresh_token: Option<String>,
+}
+
+async fn sign_in_with_cognito(
+    client: &CognitoClient,
+    user_pool_id: &str,
+    client_id: &str,
+    client_secret: Option<&str>,
+    username: &str,
+    password: &str,
+) -> Result<AuthTokens, Box<dyn std::error::Error + Send + Sync>> {
+    let mut request = client
+        .admin_initiate_auth()
+        .user_pool_id(user_pool_id)
+        .client_id(client_id)
+        .auth_flow(AuthFlowType::AdminUserPasswordAuth)
+        .auth_parameters("USERNAME", username)
+        .auth_parameters("PASSWORD", password);
+
+    // App clients configured with a secret require a SECRET_HASH on every call.
+    if let Some(secret) = client_secret {
+        let secret_hash = compute_secret_hash(username, client_id, secret);
+        request = request.auth_parameters("SECRET_HASH", secret_hash);
+    }
+
+    let result = request.send().await?;
+
+    let auth = result
+        .authentication_result
+        .ok_or("Cognito did not return an authentication result")?;
+
+    Ok(AuthTokens {
+        id_token: auth.id_token,
+        access_token: auth.access_token,
+        refresh_token: auth.refresh_token,
+    })
+}
+
+/// Computes the Cognito `SECRET_HASH` for an app client configured with a secret.
+///
+/// The hash is `base64(HMAC-SHA256(key = client_secret, message = username + client_id))`.
+fn compute_secret_hash(username: &str, client_id: &str, client_secret: &str) -> String {
+    let mut mac = HmacSha256::new_from_slice(client_secret.as_bytes())
+        .expect("HMAC can take a key of any size");
+    mac.update(username.as_bytes());
+    mac.update(client_id.as_bytes());
+    let result = mac.finalize().into_bytes();
+    base64::engine::general_purpose::STANDARD.encode(result)
+}
+
+fn create_success_response<T: Serialize>(data: T) -> Response<Body> {
+    Response::builder()
+        .status(200)
+        .header("content-type", "application/json")
+        .body(serde_json::to_string(&data).unwrap().into())
+        .unwrap()
+}
+
+fn create_error_response(status: u16, error_code: &str, message: &str) -> Response<Body> {
+    let error_response = ErrorResponse {
+        error: error_code.to_string(),
+        message: message.to_string(),
+    };
+
+    Response::builder()
+        .status(status)
+        .header("content-type", "application/json")
+        .body(serde_json::to_string(&error_response).unwrap().into())
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lambda_http::Request;
+
+    #[tokio::test]
+    async fn test_invalid_request_body() {
+        let request = Request::new(Body::Empty);
+        let response = function_handler(request).await.unwrap();
+        assert_eq!(response.status(), 400);
+    }
+
+    #[tokio::test]
+    async fn test_missing_required_fields() {
+        let body = r#"{"email": "", "password": ""}"#;
+        let request = Request::new(body.into());
+
+        let response = function_handler(request).await.unwrap();
+        assert_eq!(response.status(), 400);
+    }
+
+    #[test]
+    fn test_secret_hash_is_stable_base64() {
+        // Known-answer vector so the HMAC key/message ordering can't silently drift.
+        let hash = compute_secret_hash("alice", "client123", "s3cr3t");
+        assert_eq!(hash, "kOPeHzvKtscBvBM4zlIffwOT074cplXWBq9MCfoDa4k=");
+    }
+}
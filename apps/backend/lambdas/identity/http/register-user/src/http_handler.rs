@@ -1,8 +1,13 @@
-use lambda_http::{Body, Error, Request, Response};
+use lambda_http::{Body, Error, Request, Response, RequestExt};
+use lambda_http::request::RequestContext;
 use serde::{Deserialize, Serialize};
 use aws_sdk_cognitoidentityprovider::Client as CognitoClient;
+use aws_sdk_dynamodb::Client as DynamoClient;
+use aws_sdk_dynamodb::types::AttributeValue;
 use aws_config::load_defaults;
-use tracing::{info, error};
+use email_address::EmailAddress;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{info, warn, error};
 use uuid::Uuid;
 
 #[derive(Deserialize)]
@@ -23,6 +28,17 @@ struct RegisterUserResponse {
 struct ErrorResponse {
     error: String,
     message: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    fields: Vec<FieldError>,
+}
+
+/// A validation failure scoped to a single input field so front ends can
+/// highlight the offending control.
+#[derive(Serialize)]
+struct FieldError {
+    field: String,
+    code: String,
+    message: String,
 }
 
 pub(crate) async fn function_handler(event: Request) -> Result<Response<Body>, Error> {
@@ -38,54 +54,118 @@ pub(crate) async fn function_handler(event: Request) -> Result<Response<Body>, E
         }
     };
 
-    // Validate input
-    if request.email.is_empty() || request.password.is_empty() || request.tenant_id.is_empty() {
-        return Ok(create_error_response(400, "VALIDATION_ERROR", "Email, password, and tenant_id are required"));
+    // Validate the submitted fields against the configured policy.
+    let field_errors = validate_registration(&request);
+    if !field_errors.is_empty() {
+        return Ok(create_validation_error_response(field_errors));
     }
 
-    // Validate email format
-    if !request.email.contains('@') {
-        return Ok(create_error_response(400, "VALIDATION_ERROR", "Invalid email format"));
-    }
-
-    // Validate password strength (basic)
-    if request.password.len() < 8 {
-        return Ok(create_error_response(400, "VALIDATION_ERROR", "Password must be at least 8 characters long"));
-    }
-
-    // Initialize AWS Cognito client
+    // Initialize AWS clients
     let config = load_defaults(aws_config::BehaviorVersion::latest()).await;
     let cognito_client = CognitoClient::new(&config);
+    let dynamo_client = DynamoClient::new(&config);
 
-    // Get user pool ID from environment
+    // Throttle per source IP to prevent email enumeration and protect the
+    // Cognito admin API quotas.
+    let source_ip = source_ip(&event);
+    let rate_limiter = RateLimiter::from_env(dynamo_client.clone())?;
+    match rate_limiter.should_block_action(&source_ip).await {
+        Ok(true) => {
+            warn!("Rate limit exceeded for {}", source_ip);
+            return Ok(create_error_response(429, "RATE_LIMITED", "Too many registration attempts, please try again later"));
+        }
+        Ok(false) => {
+            if let Err(e) = rate_limiter.record_action(&source_ip).await {
+                error!("Rate limiter unavailable, allowing request: {}", e);
+            }
+        }
+        Err(e) => {
+            // Fail open: a limiter outage must not take down registration.
+            error!("Rate limiter unavailable, allowing request: {}", e);
+        }
+    }
+
+    // Read required configuration up front so a misconfigured environment fails
+    // closed, before any Cognito account is created that we'd have to roll back.
     let user_pool_id = std::env::var("USER_POOL_ID")
         .map_err(|_| "USER_POOL_ID environment variable not set")?;
+    let users_table = std::env::var("USERS_TABLE")
+        .map_err(|_| "USERS_TABLE environment variable not set")?;
 
     // Generate user ID
     let user_id = Uuid::new_v4().to_string();
+    let user_role = request.user_role.unwrap_or_else(|| "User".to_string());
 
     // Create user in Cognito
-    match register_user_in_cognito(
+    if let Err(e) = register_user_in_cognito(
         &cognito_client,
         &user_pool_id,
         &user_id,
         &request.email,
         &request.password,
         &request.tenant_id,
-        &request.user_role.unwrap_or_else(|| "User".to_string()),
+        &user_role,
     ).await {
-        Ok(_) => {
-            info!("User registered successfully: {}", user_id);
-            Ok(create_success_response(RegisterUserResponse {
-                user_id,
-                message: "User registered successfully. Please check your email for verification.".to_string(),
-            }))
+        error!("Failed to register user: {}", e);
+        return Ok(map_cognito_error(e));
+    }
+
+    // Mirror the account into the users table so tenants can be queried without
+    // paginating Cognito. If the write fails we roll the Cognito account back so
+    // the two stores don't drift.
+    let repository = DynamoUserRepository::new(dynamo_client.clone(), users_table);
+    let record = UserRecord {
+        user_id: user_id.clone(),
+        email: request.email.clone(),
+        tenant_id: request.tenant_id.clone(),
+        user_role,
+        created: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+    };
+
+    if let Err(e) = repository.put_user(&record).await {
+        error!("Failed to persist user profile, rolling back Cognito account: {}", e);
+        if let Err(rollback_err) = cognito_client
+            .admin_delete_user()
+            .user_pool_id(&user_pool_id)
+            .username(&user_id)
+            .send()
+            .await
+        {
+            error!("Rollback of Cognito account {} failed: {}", user_id, rollback_err);
         }
-        Err(e) => {
-            error!("Failed to register user: {}", e);
-            Ok(create_error_response(500, "REGISTRATION_FAILED", &e.to_string()))
+        return Ok(create_error_response(500, "REGISTRATION_FAILED", "Failed to persist user profile"));
+    }
+
+    info!("User registered successfully: {}", user_id);
+    Ok(create_success_response(RegisterUserResponse {
+        user_id,
+        message: "User registered successfully.".to_string(),
+    }))
+}
+
+/// Translates a Cognito `admin_create_user` failure into an actionable HTTP
+/// status. A duplicate email is a client-side conflict rather than a server
+/// error, and a rejected password should echo the pool's policy message back.
+fn map_cognito_error(error: Box<dyn std::error::Error + Send + Sync>) -> Response<Body> {
+    use aws_sdk_cognitoidentityprovider::operation::admin_create_user::AdminCreateUserError;
+    use aws_smithy_runtime_api::client::result::SdkError;
+
+    if let Some(sdk_err) = error.downcast_ref::<SdkError<AdminCreateUserError>>() {
+        if let Some(service_err) = sdk_err.as_service_error() {
+            if service_err.is_username_exists_exception() {
+                return create_error_response(409, "USER_EXISTS", "A user with that email already exists");
+            }
+            if let AdminCreateUserError::InvalidPasswordException(inner) = service_err {
+                let message = inner.message().unwrap_or("Password does not meet the required policy");
+                return create_error_response(400, "INVALID_PASSWORD", message);
+            }
         }
     }
+
+    create_error_response(500, "REGISTRATION_FAILED", &error.to_string())
 }
 
 async fn register_user_in_cognito(
@@ -150,6 +230,251 @@ async fn register_user_in_cognito(
     Ok(())
 }
 
+/// Password policy read from the environment, kept in sync with the Cognito
+/// pool's own policy so validation fails fast before an admin API round-trip.
+struct PasswordPolicy {
+    min_length: usize,
+    require_uppercase: bool,
+    require_lowercase: bool,
+    require_digit: bool,
+    require_symbol: bool,
+}
+
+impl PasswordPolicy {
+    fn from_env() -> Self {
+        fn flag(name: &str) -> bool {
+            std::env::var(name)
+                .map(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes"))
+                .unwrap_or(false)
+        }
+
+        Self {
+            min_length: std::env::var("PASSWORD_MIN_LENGTH")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(8),
+            require_uppercase: flag("PASSWORD_REQUIRE_UPPERCASE"),
+            require_lowercase: flag("PASSWORD_REQUIRE_LOWERCASE"),
+            require_digit: flag("PASSWORD_REQUIRE_DIGIT"),
+            require_symbol: flag("PASSWORD_REQUIRE_SYMBOL"),
+        }
+    }
+
+    fn check(&self, password: &str, errors: &mut Vec<FieldError>) {
+        if password.chars().count() < self.min_length {
+            errors.push(FieldError {
+                field: "password".to_string(),
+                code: "TOO_SHORT".to_string(),
+                message: format!("Password must be at least {} characters long", self.min_length),
+            });
+        }
+        if self.require_uppercase && !password.chars().any(|c| c.is_ascii_uppercase()) {
+            errors.push(FieldError {
+                field: "password".to_string(),
+                code: "MISSING_UPPERCASE".to_string(),
+                message: "Password must contain an uppercase letter".to_string(),
+            });
+        }
+        if self.require_lowercase && !password.chars().any(|c| c.is_ascii_lowercase()) {
+            errors.push(FieldError {
+                field: "password".to_string(),
+                code: "MISSING_LOWERCASE".to_string(),
+                message: "Password must contain a lowercase letter".to_string(),
+            });
+        }
+        if self.require_digit && !password.chars().any(|c| c.is_ascii_digit()) {
+            errors.push(FieldError {
+                field: "password".to_string(),
+                code: "MISSING_DIGIT".to_string(),
+                message: "Password must contain a digit".to_string(),
+            });
+        }
+        if self.require_symbol && !password.chars().any(|c| !c.is_alphanumeric()) {
+            errors.push(FieldError {
+                field: "password".to_string(),
+                code: "MISSING_SYMBOL".to_string(),
+                message: "Password must contain a symbol".to_string(),
+            });
+        }
+    }
+}
+
+/// Validates a registration request, returning one [`FieldError`] per failed
+/// check (a field may fail several) so the whole form can be reported in a
+/// single response.
+fn validate_registration(request: &RegisterUserRequest) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+
+    if request.tenant_id.is_empty() {
+        errors.push(FieldError {
+            field: "tenant_id".to_string(),
+            code: "REQUIRED".to_string(),
+            message: "tenant_id is required".to_string(),
+        });
+    }
+
+    if !EmailAddress::is_valid(&request.email) {
+        errors.push(FieldError {
+            field: "email".to_string(),
+            code: "INVALID".to_string(),
+            message: "Invalid email address".to_string(),
+        });
+    }
+
+    PasswordPolicy::from_env().check(&request.password, &mut errors);
+
+    errors
+}
+
+/// A user profile mirrored into DynamoDB alongside the Cognito account.
+struct UserRecord {
+    user_id: String,
+    email: String,
+    tenant_id: String,
+    user_role: String,
+    created: u64,
+}
+
+/// Persistence for user profile records.
+#[async_trait::async_trait]
+trait UserRepository {
+    async fn put_user(&self, record: &UserRecord) -> Result<(), Error>;
+}
+
+/// `aws-sdk-dynamodb`-backed [`UserRepository`].
+struct DynamoUserRepository {
+    client: DynamoClient,
+    table_name: String,
+}
+
+impl DynamoUserRepository {
+    fn new(client: DynamoClient, table_name: String) -> Self {
+        Self { client, table_name }
+    }
+}
+
+#[async_trait::async_trait]
+impl UserRepository for DynamoUserRepository {
+    async fn put_user(&self, record: &UserRecord) -> Result<(), Error> {
+        self.client
+            .put_item()
+            .table_name(&self.table_name)
+            .item("userID", AttributeValue::S(record.user_id.clone()))
+            .item("email", AttributeValue::S(record.email.clone()))
+            .item("tenant_id", AttributeValue::S(record.tenant_id.clone()))
+            .item("user_role", AttributeValue::S(record.user_role.clone()))
+            .item("created", AttributeValue::N(record.created.to_string()))
+            .send()
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Extracts the caller's source IP for rate-limiting.
+///
+/// The request context identity is set by API Gateway and cannot be spoofed, so
+/// it is the primary key. We only fall back to the *last* `X-Forwarded-For` hop
+/// (the one API Gateway appends) — never the client-supplied first entry, which
+/// an attacker could randomize per request to evade the limiter.
+fn source_ip(event: &Request) -> String {
+    if let RequestContext::ApiGatewayV1(ctx) = event.request_context() {
+        if let Some(ip) = ctx.identity.source_ip {
+            return ip;
+        }
+    }
+
+    if let Some(forwarded) = event.headers().get("x-forwarded-for") {
+        if let Ok(value) = forwarded.to_str() {
+            if let Some(last) = value.split(',').next_back() {
+                let ip = last.trim();
+                if !ip.is_empty() {
+                    return ip.to_string();
+                }
+            }
+        }
+    }
+
+    "unknown".to_string()
+}
+
+/// DynamoDB-backed fixed-window counter that throttles actions per key (source IP).
+///
+/// Each fixed window is a distinct item whose partition key embeds the window
+/// index, and every item carries a TTL so expired windows are reaped by DynamoDB
+/// rather than by us. The limit and window are environment-configurable so the
+/// same limiter can front the sign-in handler.
+struct RateLimiter {
+    client: DynamoClient,
+    table_name: String,
+    max_attempts: u32,
+    window_secs: u64,
+}
+
+impl RateLimiter {
+    fn from_env(client: DynamoClient) -> Result<Self, Error> {
+        let table_name = std::env::var("RATE_LIMIT_TABLE")
+            .map_err(|_| "RATE_LIMIT_TABLE environment variable not set")?;
+        let max_attempts = std::env::var("RATE_LIMIT_MAX_ATTEMPTS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(5);
+        let window_secs = std::env::var("RATE_LIMIT_WINDOW_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(900);
+
+        Ok(Self { client, table_name, max_attempts, window_secs })
+    }
+
+    fn now_secs() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0)
+    }
+
+    /// Key for the window the current instant falls into.
+    fn window_key(&self, key: &str) -> String {
+        format!("{}:{}", key, Self::now_secs() / self.window_secs)
+    }
+
+    async fn should_block_action(&self, key: &str) -> Result<bool, Error> {
+        let result = self
+            .client
+            .get_item()
+            .table_name(&self.table_name)
+            .key("rate_key", AttributeValue::S(self.window_key(key)))
+            .send()
+            .await?;
+
+        let attempts = result
+            .item()
+            .and_then(|item| item.get("attempts"))
+            .and_then(|value| value.as_n().ok())
+            .and_then(|n| n.parse::<u32>().ok())
+            .unwrap_or(0);
+
+        Ok(attempts >= self.max_attempts)
+    }
+
+    async fn record_action(&self, key: &str) -> Result<(), Error> {
+        let expires_at = Self::now_secs() + self.window_secs;
+
+        self.client
+            .update_item()
+            .table_name(&self.table_name)
+            .key("rate_key", AttributeValue::S(self.window_key(key)))
+            .update_expression("ADD attempts :one SET expires_at = if_not_exists(expires_at, :ttl)")
+            .expression_attribute_values(":one", AttributeValue::N("1".to_string()))
+            .expression_attribute_values(":ttl", AttributeValue::N(expires_at.to_string()))
+            .send()
+            .await?;
+
+        Ok(())
+    }
+}
+
 fn create_success_response<T: Serialize>(data: T) -> Response<Body> {
     Response::builder()
         .status(201)
@@ -162,6 +487,7 @@ fn create_error_response(status: u16, error_code: &str, message: &str) -> Respon
     let error_response = ErrorResponse {
         error: error_code.to_string(),
         message: message.to_string(),
+        fields: Vec::new(),
     };
 
     Response::builder()
@@ -171,6 +497,20 @@ fn create_error_response(status: u16, error_code: &str, message: &str) -> Respon
         .unwrap()
 }
 
+fn create_validation_error_response(fields: Vec<FieldError>) -> Response<Body> {
+    let error_response = ErrorResponse {
+        error: "VALIDATION_ERROR".to_string(),
+        message: "One or more fields are invalid".to_string(),
+        fields,
+    };
+
+    Response::builder()
+        .status(400)
+        .header("content-type", "application/json")
+        .body(serde_json::to_string(&error_response).unwrap().into())
+        .unwrap()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;